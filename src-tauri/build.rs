@@ -0,0 +1,18 @@
+// Regenerates `schema.json` from `ObfuscationConfig` whenever `src/config.rs`
+// changes (see the `rerun-if-changed` line below), so the
+// Tauri frontend always has an up-to-date JSON Schema for form validation
+// and autogenerated forms without needing to hand-sync it with the Rust
+// types. The types themselves live in `src/config.rs`; we `include!` that
+// module here rather than depending on the crate itself (build scripts
+// compile before the crate they build for).
+include!("src/config.rs");
+
+fn main() {
+    let schema = config_schema();
+    let json = serde_json::to_string_pretty(&schema).expect("failed to serialize JSON schema");
+
+    let out_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("schema.json");
+    std::fs::write(&out_path, json).expect("failed to write schema.json");
+
+    println!("cargo:rerun-if-changed=src/config.rs");
+}
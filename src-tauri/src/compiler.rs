@@ -0,0 +1,147 @@
+use crate::config::CompilerConfig;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Output of a successful compilation.
+#[derive(Debug, Clone)]
+pub struct CompileOutput {
+    pub output_path: String,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Errors that can occur while locating or invoking the compiler toolchain.
+#[derive(Debug)]
+pub enum CompilerError {
+    ToolchainNotFound(String),
+    CompilationFailed { exit_code: Option<i32>, stderr: String },
+    Io(String),
+}
+
+impl std::fmt::Display for CompilerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompilerError::ToolchainNotFound(msg) => write!(f, "Toolchain not found: {}", msg),
+            CompilerError::CompilationFailed { exit_code, stderr } => {
+                write!(f, "Compilation failed (exit code {:?}): {}", exit_code, stderr)
+            }
+            CompilerError::Io(msg) => write!(f, "I/O error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CompilerError {}
+
+/// Compiles `source_path` into `output_path` by spawning the configured (or
+/// auto-detected) `clang++`/`g++` toolchain, honoring the optimization
+/// level, target triple, sanitizer toggles, and debug-info preference in
+/// `config`/`preserve_debug_info`.
+pub fn compile(
+    source_path: &Path,
+    output_path: &Path,
+    config: &CompilerConfig,
+    preserve_debug_info: bool,
+) -> Result<CompileOutput, CompilerError> {
+    let compiler_path = resolve_compiler_path(config)?;
+
+    let mut cmd = Command::new(&compiler_path);
+    cmd.arg(source_path).arg("-o").arg(output_path);
+    cmd.arg(config.optimization_level.as_flag());
+    cmd.arg(if preserve_debug_info { "-g" } else { "-g0" });
+
+    if let Some(triple) = &config.target_triple {
+        cmd.arg(format!("--target={}", triple));
+    }
+
+    for flag in config.sanitizers.to_compiler_flags() {
+        cmd.arg(flag);
+    }
+
+    if let Some(llvm_bin_path) = &config.llvm_bin_path {
+        cmd.env("PATH", prepend_to_path(llvm_bin_path));
+    }
+
+    let output = cmd
+        .output()
+        .map_err(|e| CompilerError::Io(format!("Failed to spawn {}: {}", compiler_path.display(), e)))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+    if !output.status.success() {
+        return Err(CompilerError::CompilationFailed {
+            exit_code: output.status.code(),
+            stderr,
+        });
+    }
+
+    Ok(CompileOutput {
+        output_path: output_path.display().to_string(),
+        stdout,
+        stderr,
+    })
+}
+
+/// Resolves the compiler binary to invoke: an explicit `clang_path` if
+/// configured, otherwise the first of `clang++`/`g++` found on `$PATH`.
+/// Validates that the resulting binary actually exists before returning.
+fn resolve_compiler_path(config: &CompilerConfig) -> Result<PathBuf, CompilerError> {
+    if let Some(path) = &config.clang_path {
+        if !path.is_file() {
+            return Err(CompilerError::ToolchainNotFound(format!(
+                "Configured clang_path does not exist: {}",
+                path.display()
+            )));
+        }
+        return Ok(path.clone());
+    }
+
+    for candidate in ["clang++", "g++"] {
+        if let Some(path) = find_on_path(candidate) {
+            return Ok(path);
+        }
+    }
+
+    Err(CompilerError::ToolchainNotFound(
+        "Neither a configured clang_path nor clang++/g++ were found on PATH".to_string(),
+    ))
+}
+
+/// Searches `$PATH` for an executable named `name`.
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths).find_map(|dir| {
+        let candidate = dir.join(name);
+        candidate.is_file().then_some(candidate)
+    })
+}
+
+/// Prepends `dir` to the current `$PATH`, so a configured LLVM bin directory
+/// takes priority when the compiler shells out to other LLVM tools (e.g. `ld.lld`).
+fn prepend_to_path(dir: &Path) -> std::ffi::OsString {
+    let existing = std::env::var_os("PATH").unwrap_or_default();
+    let mut paths: Vec<PathBuf> = vec![dir.to_path_buf()];
+    paths.extend(std::env::split_paths(&existing));
+    std::env::join_paths(paths).unwrap_or_else(|_| existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_compiler_path_rejects_missing_clang_path() {
+        let mut config = CompilerConfig::default();
+        config.clang_path = Some(PathBuf::from("/nonexistent/clang++"));
+
+        let err = resolve_compiler_path(&config).unwrap_err();
+        assert!(matches!(err, CompilerError::ToolchainNotFound(_)));
+    }
+
+    #[test]
+    fn test_prepend_to_path_puts_llvm_bin_first() {
+        let result = prepend_to_path(Path::new("/opt/llvm/bin"));
+        let first = std::env::split_paths(&result).next().unwrap();
+        assert_eq!(first, PathBuf::from("/opt/llvm/bin"));
+    }
+}
@@ -1,11 +1,12 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents the intensity level of obfuscation transformations.
 /// Higher intensity provides stronger protection but may increase binary size
 /// and compilation time.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum ObfuscationIntensity {
     /// Minimal obfuscation with negligible performance impact
     Low,
@@ -25,7 +26,7 @@ impl Default for ObfuscationIntensity {
 
 /// Configuration for specific obfuscation techniques.
 /// Each technique can be individually enabled or disabled.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TechniqueConfig {
     /// Enable control flow flattening to obscure program logic
     pub control_flow_flattening: bool,
@@ -85,26 +86,149 @@ impl TechniqueConfig {
     }
 }
 
+/// Optimization level passed to the compiler via `-O<level>`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub enum OptimizationLevel {
+    O0,
+    O1,
+    O2,
+    O3,
+    Os,
+}
+
+impl OptimizationLevel {
+    /// The compiler flag for this level (e.g. `-O2`).
+    pub fn as_flag(&self) -> &'static str {
+        match self {
+            Self::O0 => "-O0",
+            Self::O1 => "-O1",
+            Self::O2 => "-O2",
+            Self::O3 => "-O3",
+            Self::Os => "-Os",
+        }
+    }
+}
+
+impl Default for OptimizationLevel {
+    fn default() -> Self {
+        Self::O2
+    }
+}
+
+/// A bitset of compiler sanitizer/hardening toggles, so multiple can be
+/// combined (e.g. address sanitizer + stack protector) in a single config value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SanitizerSet(u8);
+
+impl SanitizerSet {
+    pub const NONE: Self = Self(0);
+    pub const ADDRESS: Self = Self(1 << 0);
+    pub const UNDEFINED_BEHAVIOR: Self = Self(1 << 1);
+    pub const STACK_PROTECTOR: Self = Self(1 << 2);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Translates the enabled toggles into the clang/gcc flags that implement them.
+    pub fn to_compiler_flags(self) -> Vec<String> {
+        let mut sanitizers = Vec::new();
+        if self.contains(Self::ADDRESS) {
+            sanitizers.push("address");
+        }
+        if self.contains(Self::UNDEFINED_BEHAVIOR) {
+            sanitizers.push("undefined");
+        }
+
+        let mut flags = Vec::new();
+        if !sanitizers.is_empty() {
+            flags.push(format!("-fsanitize={}", sanitizers.join(",")));
+        }
+        if self.contains(Self::STACK_PROTECTOR) {
+            flags.push("-fstack-protector-strong".to_string());
+        }
+        flags
+    }
+}
+
+impl std::ops::BitOr for SanitizerSet {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl Default for SanitizerSet {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Configuration for the compilation backend (toolchain location and flags).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompilerConfig {
+    /// Explicit path to the `clang++`/`g++` binary; searches `$PATH` when unset
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub clang_path: Option<PathBuf>,
+
+    /// Explicit path to the LLVM bin directory (e.g. for `ld.lld`, `llvm-objcopy`)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub llvm_bin_path: Option<PathBuf>,
+
+    /// Target triple to compile for (e.g. `x86_64-unknown-linux-gnu`); uses the host triple when unset
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub target_triple: Option<String>,
+
+    /// Optimization level passed via `-O<n>`
+    pub optimization_level: OptimizationLevel,
+
+    /// Sanitizer and hardening toggles to enable during compilation
+    pub sanitizers: SanitizerSet,
+}
+
+impl Default for CompilerConfig {
+    fn default() -> Self {
+        Self {
+            clang_path: None,
+            llvm_bin_path: None,
+            target_triple: None,
+            optimization_level: OptimizationLevel::default(),
+            sanitizers: SanitizerSet::NONE,
+        }
+    }
+}
+
 /// Main configuration structure for the obfuscation process
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ObfuscationConfig {
     /// Overall intensity level
     pub intensity: ObfuscationIntensity,
-    
+
     /// Individual technique toggles
     pub techniques: TechniqueConfig,
-    
+
     /// Preserve debug symbols in output binary
     pub preserve_debug_info: bool,
-    
+
     /// Generate detailed obfuscation report
     pub generate_report: bool,
-    
+
     /// Random seed for reproducible obfuscation (None = random)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub seed: Option<u64>,
-    
-    /// Maximum increase in binary size (percentage)
+
+    /// Maximum increase in binary size (percentage). Must be at least 100
+    /// (i.e. the budget may not be set below the original size).
+    #[schemars(range(min = 100))]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub max_size_increase: Option<u32>,
+
+    /// Compilation backend toolchain and flags
+    pub compiler: CompilerConfig,
 }
 
 impl Default for ObfuscationConfig {
@@ -116,6 +240,7 @@ impl Default for ObfuscationConfig {
             generate_report: true,
             seed: None,
             max_size_increase: Some(150), // Allow up to 150% of original size
+            compiler: CompilerConfig::default(),
         }
     }
 }
@@ -144,28 +269,59 @@ impl ObfuscationConfig {
         }
     }
 
-    /// Loads configuration from a JSON file
+    /// Loads configuration from a file, detecting JSON/TOML/YAML from its extension
     pub fn from_file(path: &PathBuf) -> Result<Self, ConfigError> {
+        let format = ConfigFormat::from_extension(path)?;
+
         let content = fs::read_to_string(path)
             .map_err(|e| ConfigError::IoError(format!("Failed to read config file: {}", e)))?;
-        
-        let config: ObfuscationConfig = serde_json::from_str(&content)
-            .map_err(|e| ConfigError::ParseError(format!("Invalid config format: {}", e)))?;
-        
-        Ok(config)
+
+        Self::from_str_with_format(&content, format)
+    }
+
+    /// Parses configuration from an in-memory string in an explicitly chosen
+    /// format. Useful for stdin or other in-memory content where there is no
+    /// file extension to detect the format from.
+    pub fn from_str_with_format(content: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        match format {
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| {
+                ConfigError::ParseError(format!("Invalid {} config: {}", format.name(), e))
+            }),
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| {
+                ConfigError::ParseError(format!("Invalid {} config: {}", format.name(), e))
+            }),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| {
+                ConfigError::ParseError(format!("Invalid {} config: {}", format.name(), e))
+            }),
+        }
     }
 
-    /// Saves configuration to a JSON file
+    /// Saves configuration to a file, detecting JSON/TOML/YAML from its extension
     pub fn save_to_file(&self, path: &PathBuf) -> Result<(), ConfigError> {
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| ConfigError::SerializeError(format!("Failed to serialize config: {}", e)))?;
-        
-        fs::write(path, json)
+        let format = ConfigFormat::from_extension(path)?;
+        let content = self.to_string_with_format(format)?;
+
+        fs::write(path, content)
             .map_err(|e| ConfigError::IoError(format!("Failed to write config file: {}", e)))?;
-        
+
         Ok(())
     }
 
+    /// Serializes configuration to a string in an explicitly chosen format.
+    pub fn to_string_with_format(&self, format: ConfigFormat) -> Result<String, ConfigError> {
+        match format {
+            ConfigFormat::Json => serde_json::to_string_pretty(self).map_err(|e| {
+                ConfigError::SerializeError(format!("Failed to serialize {} config: {}", format.name(), e))
+            }),
+            ConfigFormat::Toml => toml::to_string_pretty(self).map_err(|e| {
+                ConfigError::SerializeError(format!("Failed to serialize {} config: {}", format.name(), e))
+            }),
+            ConfigFormat::Yaml => serde_yaml::to_string(self).map_err(|e| {
+                ConfigError::SerializeError(format!("Failed to serialize {} config: {}", format.name(), e))
+            }),
+        }
+    }
+
     /// Validates the configuration for correctness
     pub fn validate(&self) -> Result<(), ConfigError> {
         if let Some(max_increase) = self.max_size_increase {
@@ -198,6 +354,348 @@ impl ObfuscationConfig {
     }
 }
 
+/// File formats `ObfuscationConfig` can be loaded from and saved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detects the format from a file's extension (`.json`, `.toml`, `.yaml`/`.yml`).
+    pub fn from_extension(path: &Path) -> Result<Self, ConfigError> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Self::Json),
+            Some("toml") => Ok(Self::Toml),
+            Some("yaml") | Some("yml") => Ok(Self::Yaml),
+            Some(other) => Err(ConfigError::ParseError(format!(
+                "Unsupported config file extension: .{}",
+                other
+            ))),
+            None => Err(ConfigError::ParseError(
+                "Config file has no extension to detect its format".to_string(),
+            )),
+        }
+    }
+
+    /// The human-readable name used in error messages.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Toml => "TOML",
+            Self::Yaml => "YAML",
+        }
+    }
+}
+
+/// Identifies which configuration layer a resolved field's value came from.
+///
+/// Layers are applied in increasing priority: `Default` < `System` < `User`
+/// < `Project` < `Override`. Each variant (other than `Default`/`Override`)
+/// carries the path of the file it was read from, so the UI can show the
+/// user exactly which file is responsible for a given setting.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum LayerSource {
+    /// Built-in default baked into the application
+    Default,
+    /// System-wide configuration file
+    System(PathBuf),
+    /// Per-user configuration file (`$XDG_CONFIG_HOME/obfussor/config.json`, or `$HOME/.config/obfussor/config.json` when that is unset)
+    User(PathBuf),
+    /// Project-level configuration discovered by walking up from the input file
+    Project(PathBuf),
+    /// Explicit override supplied by the caller
+    Override,
+}
+
+/// Every field of `TechniqueConfig`, optional so a layer can override just
+/// the techniques it cares about and leave the rest untouched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialTechniqueConfig {
+    pub control_flow_flattening: Option<bool>,
+    pub string_encryption: Option<bool>,
+    pub bogus_code_injection: Option<bool>,
+    pub instruction_substitution: Option<bool>,
+    pub function_manipulation: Option<bool>,
+    pub opaque_predicates: Option<bool>,
+}
+
+/// Every field of `ObfuscationConfig`, optional so a layer (system, user,
+/// project, or an explicit override) can specify only the keys it wants to
+/// change. Missing keys fall through to whatever the lower-priority layers
+/// already resolved.
+///
+/// Note: `seed` and `max_size_increase` use `Option` purely to mean "this
+/// layer doesn't set this field" — there's no way for a higher-priority
+/// layer (e.g. an override) to explicitly reset either one back to its
+/// unset state (random seed / no size budget) once a lower layer has set a
+/// value, since `None` is indistinguishable from "not specified here". If a
+/// caller ever needs to explicitly clear one of these through the override
+/// path, these two fields would need to become `Option<Option<_>>`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialObfuscationConfig {
+    pub intensity: Option<ObfuscationIntensity>,
+    pub techniques: Option<PartialTechniqueConfig>,
+    pub preserve_debug_info: Option<bool>,
+    pub generate_report: Option<bool>,
+    pub seed: Option<u64>,
+    pub max_size_increase: Option<u32>,
+    pub compiler: Option<CompilerConfig>,
+}
+
+/// A single layer contributing to a resolved `ObfuscationConfig`, pairing
+/// the partial settings it specifies with where they came from.
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub source: LayerSource,
+    pub config: PartialObfuscationConfig,
+}
+
+/// Per-field origin, mirroring the shape of `TechniqueConfig`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TechniqueOrigins {
+    pub control_flow_flattening: LayerSource,
+    pub string_encryption: LayerSource,
+    pub bogus_code_injection: LayerSource,
+    pub instruction_substitution: LayerSource,
+    pub function_manipulation: LayerSource,
+    pub opaque_predicates: LayerSource,
+}
+
+impl TechniqueOrigins {
+    fn all(source: LayerSource) -> Self {
+        Self {
+            control_flow_flattening: source.clone(),
+            string_encryption: source.clone(),
+            bogus_code_injection: source.clone(),
+            instruction_substitution: source.clone(),
+            function_manipulation: source.clone(),
+            opaque_predicates: source,
+        }
+    }
+}
+
+/// Per-field origin, mirroring the shape of `ObfuscationConfig`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldOrigins {
+    pub intensity: LayerSource,
+    pub techniques: TechniqueOrigins,
+    pub preserve_debug_info: LayerSource,
+    pub generate_report: LayerSource,
+    pub seed: LayerSource,
+    pub max_size_increase: LayerSource,
+    pub compiler: LayerSource,
+}
+
+impl FieldOrigins {
+    fn all(source: LayerSource) -> Self {
+        Self {
+            intensity: source.clone(),
+            techniques: TechniqueOrigins::all(source.clone()),
+            preserve_debug_info: source.clone(),
+            generate_report: source.clone(),
+            seed: source.clone(),
+            max_size_increase: source.clone(),
+            compiler: source,
+        }
+    }
+}
+
+/// An `ObfuscationConfig` resolved from one or more layers, together with
+/// the origin of every field so callers can explain *why* a setting has
+/// the value it does.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedConfig {
+    pub config: ObfuscationConfig,
+    pub origins: FieldOrigins,
+}
+
+impl ResolvedConfig {
+    /// Starts resolution from the built-in defaults, with every field
+    /// attributed to `LayerSource::Default`.
+    fn from_defaults() -> Self {
+        Self {
+            config: ObfuscationConfig::default(),
+            origins: FieldOrigins::all(LayerSource::Default),
+        }
+    }
+
+    /// Overlays `layer` on top of the current resolution, overwriting only
+    /// the fields the layer actually specifies and recording their origin.
+    fn apply_layer(&mut self, layer: &ConfigLayer) {
+        let source = &layer.source;
+        let partial = &layer.config;
+
+        if let Some(intensity) = partial.intensity {
+            self.config.intensity = intensity;
+            self.origins.intensity = source.clone();
+        }
+        if let Some(techniques) = &partial.techniques {
+            if let Some(v) = techniques.control_flow_flattening {
+                self.config.techniques.control_flow_flattening = v;
+                self.origins.techniques.control_flow_flattening = source.clone();
+            }
+            if let Some(v) = techniques.string_encryption {
+                self.config.techniques.string_encryption = v;
+                self.origins.techniques.string_encryption = source.clone();
+            }
+            if let Some(v) = techniques.bogus_code_injection {
+                self.config.techniques.bogus_code_injection = v;
+                self.origins.techniques.bogus_code_injection = source.clone();
+            }
+            if let Some(v) = techniques.instruction_substitution {
+                self.config.techniques.instruction_substitution = v;
+                self.origins.techniques.instruction_substitution = source.clone();
+            }
+            if let Some(v) = techniques.function_manipulation {
+                self.config.techniques.function_manipulation = v;
+                self.origins.techniques.function_manipulation = source.clone();
+            }
+            if let Some(v) = techniques.opaque_predicates {
+                self.config.techniques.opaque_predicates = v;
+                self.origins.techniques.opaque_predicates = source.clone();
+            }
+        }
+        if let Some(v) = partial.preserve_debug_info {
+            self.config.preserve_debug_info = v;
+            self.origins.preserve_debug_info = source.clone();
+        }
+        if let Some(v) = partial.generate_report {
+            self.config.generate_report = v;
+            self.origins.generate_report = source.clone();
+        }
+        if let Some(v) = partial.seed {
+            self.config.seed = Some(v);
+            self.origins.seed = source.clone();
+        }
+        if let Some(v) = partial.max_size_increase {
+            self.config.max_size_increase = Some(v);
+            self.origins.max_size_increase = source.clone();
+        }
+        if let Some(compiler) = &partial.compiler {
+            self.config.compiler = compiler.clone();
+            self.origins.compiler = source.clone();
+        }
+    }
+}
+
+/// Returns the system-wide config path, if this platform has a conventional
+/// one. There is no universal equivalent on Windows, so this is a no-op there.
+#[cfg(unix)]
+fn system_config_path() -> Option<PathBuf> {
+    Some(PathBuf::from("/etc/obfussor/config.json"))
+}
+
+#[cfg(not(unix))]
+fn system_config_path() -> Option<PathBuf> {
+    None
+}
+
+/// Returns the per-user config path, following the XDG base directory spec
+/// (`$XDG_CONFIG_HOME`, falling back to `$HOME/.config`).
+fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("obfussor").join("config.json"))
+}
+
+/// Walks up from `start` looking for a `.obfussor.json` project config,
+/// stopping at the first one found or at the filesystem root.
+fn find_project_config(start: &Path) -> Option<PathBuf> {
+    let mut dir = if start.is_dir() {
+        Some(start)
+    } else {
+        start.parent()
+    };
+
+    while let Some(current) = dir {
+        let candidate = current.join(".obfussor.json");
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = current.parent();
+    }
+
+    None
+}
+
+/// Reads a single JSON layer file, if it exists. Returns `Ok(None)` when the
+/// file is absent so callers can skip layers that simply aren't configured.
+fn load_partial_layer(
+    path: &Path,
+    source: LayerSource,
+) -> Result<Option<ConfigLayer>, ConfigError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| {
+        ConfigError::IoError(format!("Failed to read config layer {}: {}", path.display(), e))
+    })?;
+
+    let config: PartialObfuscationConfig = serde_json::from_str(&content).map_err(|e| {
+        ConfigError::ParseError(format!("Invalid config layer {}: {}", path.display(), e))
+    })?;
+
+    Ok(Some(ConfigLayer { source, config }))
+}
+
+/// Resolves a final `ObfuscationConfig` by overlaying, in priority order:
+/// the built-in defaults, a system-wide config, a per-user config, a
+/// project-level config discovered by walking up from `input_path`, and
+/// finally `overrides` supplied directly by the caller. Later layers win
+/// key-by-key, and every resolved field keeps track of which layer it came
+/// from.
+pub fn resolve_config(
+    input_path: Option<&Path>,
+    overrides: Option<PartialObfuscationConfig>,
+) -> Result<ResolvedConfig, ConfigError> {
+    let mut resolved = ResolvedConfig::from_defaults();
+
+    if let Some(system_path) = system_config_path() {
+        if let Some(layer) = load_partial_layer(&system_path, LayerSource::System(system_path.clone()))? {
+            resolved.apply_layer(&layer);
+        }
+    }
+
+    if let Some(user_path) = user_config_path() {
+        if let Some(layer) = load_partial_layer(&user_path, LayerSource::User(user_path.clone()))? {
+            resolved.apply_layer(&layer);
+        }
+    }
+
+    if let Some(input_path) = input_path {
+        if let Some(project_path) = find_project_config(input_path) {
+            if let Some(layer) =
+                load_partial_layer(&project_path, LayerSource::Project(project_path.clone()))?
+            {
+                resolved.apply_layer(&layer);
+            }
+        }
+    }
+
+    if let Some(overrides) = overrides {
+        resolved.apply_layer(&ConfigLayer {
+            source: LayerSource::Override,
+            config: overrides,
+        });
+    }
+
+    Ok(resolved)
+}
+
+/// Derives a JSON Schema for `ObfuscationConfig` (including `TechniqueConfig`
+/// and `ObfuscationIntensity`) directly from the structs via `schemars`, so
+/// the schema can never drift out of sync with the Rust types. Used both by
+/// the `build.rs` step that snapshots `schema.json` for the frontend and by
+/// the `get_config_schema` command for clients that want it live.
+pub fn config_schema() -> serde_json::Value {
+    let schema = schemars::schema_for!(ObfuscationConfig);
+    serde_json::to_value(schema).expect("ObfuscationConfig schema is always valid JSON")
+}
+
 /// Errors that can occur during configuration handling
 #[derive(Debug)]
 pub enum ConfigError {
@@ -252,4 +750,82 @@ mod tests {
         config.max_size_increase = Some(50);
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_resolve_config_defaults_only() {
+        let resolved = resolve_config(None, None).unwrap();
+        assert_eq!(resolved.config.intensity, ObfuscationIntensity::Medium);
+        assert_eq!(resolved.origins.intensity, LayerSource::Default);
+    }
+
+    #[test]
+    fn test_resolve_config_override_wins_and_is_attributed() {
+        let overrides = PartialObfuscationConfig {
+            intensity: Some(ObfuscationIntensity::High),
+            ..Default::default()
+        };
+        let resolved = resolve_config(None, Some(overrides)).unwrap();
+        assert_eq!(resolved.config.intensity, ObfuscationIntensity::High);
+        assert_eq!(resolved.origins.intensity, LayerSource::Override);
+        // Fields untouched by the override still trace back to the default layer
+        assert_eq!(resolved.origins.generate_report, LayerSource::Default);
+    }
+
+    #[test]
+    fn test_resolve_config_project_layer_discovered_by_walking_up() {
+        let dir = std::env::temp_dir().join(format!(
+            "obfussor_test_project_{:?}",
+            std::thread::current().id()
+        ));
+        let nested = dir.join("src").join("deep");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(
+            dir.join(".obfussor.json"),
+            r#"{"generate_report": false}"#,
+        )
+        .unwrap();
+
+        let input_file = nested.join("main.cpp");
+        let resolved = resolve_config(Some(&input_file), None).unwrap();
+
+        assert!(!resolved.config.generate_report);
+        assert!(matches!(
+            resolved.origins.generate_report,
+            LayerSource::Project(_)
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_detection_from_extension() {
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("config.json")).unwrap(),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("config.toml")).unwrap(),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_extension(Path::new("config.yml")).unwrap(),
+            ConfigFormat::Yaml
+        );
+        assert!(ConfigFormat::from_extension(Path::new("config.ini")).is_err());
+    }
+
+    #[test]
+    fn test_round_trip_toml_and_yaml() {
+        let config = ObfuscationConfig::new();
+
+        let toml = config.to_string_with_format(ConfigFormat::Toml).unwrap();
+        let from_toml =
+            ObfuscationConfig::from_str_with_format(&toml, ConfigFormat::Toml).unwrap();
+        assert_eq!(from_toml.intensity, config.intensity);
+
+        let yaml = config.to_string_with_format(ConfigFormat::Yaml).unwrap();
+        let from_yaml =
+            ObfuscationConfig::from_str_with_format(&yaml, ConfigFormat::Yaml).unwrap();
+        assert_eq!(from_yaml.intensity, config.intensity);
+    }
 }
@@ -1,6 +1,12 @@
+mod compiler;
 mod config;
+mod prng;
+mod report;
+mod string_encryption;
 
-use config::{ObfuscationConfig, ObfuscationIntensity};
+use config::{ObfuscationConfig, ObfuscationIntensity, PartialObfuscationConfig, ResolvedConfig};
+use prng::Prng;
+use report::{ObfuscationReport, ObfuscationResult, TechniqueReport};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -70,9 +76,9 @@ fn greet(name: &str) -> String {
 fn obfuscate_code(
     code: String,
     config: Option<ObfuscationConfig>,
-) -> Result<String, ObfuscationError> {
+) -> Result<ObfuscationResult, ObfuscationError> {
     let config = config.unwrap_or_default();
-    
+
     // Validate configuration
     config.validate().map_err(|e| {
         ObfuscationError::new(ErrorKind::ConfigurationError, "Invalid configuration")
@@ -81,14 +87,36 @@ fn obfuscate_code(
 
     // TODO: Replace with actual LLVM obfuscation
     // For now, use enhanced mock transformation
-    let obfuscated = mock_obfuscate(&code, &config)?;
-    Ok(obfuscated)
+    let (obfuscated, report) = mock_obfuscate(&code, &config)?;
+    Ok(ObfuscationResult {
+        code: obfuscated,
+        report: if config.generate_report { Some(report) } else { None },
+    })
+}
+
+/// POSIX ownership/permission bits to apply to a saved file. Has no effect
+/// on Windows, which has no equivalent concept.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileOptions {
+    /// Octal file mode (e.g. `0o600`). Passed to `open(2)` at creation time
+    /// and then re-applied with `fchmod` after the write, so a newly created
+    /// file never exists on disk with broader (umask-default) permissions
+    /// than requested.
+    pub mode: Option<u32>,
+    /// Numeric owner uid to `chown` to
+    pub owner: Option<u32>,
+    /// Numeric group gid to `chown` to
+    pub group: Option<u32>,
 }
 
 #[tauri::command]
-fn save_file(path: String, content: String) -> Result<(), ObfuscationError> {
+fn save_file(
+    path: String,
+    content: String,
+    options: Option<FileOptions>,
+) -> Result<(), ObfuscationError> {
     let path = PathBuf::from(path);
-    
+
     // Validate path
     if path.exists() && path.is_dir() {
         return Err(ObfuscationError::new(
@@ -104,7 +132,86 @@ fn save_file(path: String, content: String) -> Result<(), ObfuscationError> {
         }
     }
 
-    fs::write(&path, content)?;
+    let mode = options.as_ref().and_then(|o| o.mode);
+    write_with_mode(&path, &content, mode)?;
+
+    if let Some(options) = options {
+        apply_file_ownership(&path, &options)?;
+    }
+
+    Ok(())
+}
+
+/// Creates (or truncates) `path` and writes `content` to it, scoping the
+/// file to `mode` from the very first syscall that brings it into existence
+/// on Unix. This closes the TOCTOU window a `fs::write` followed by a later
+/// `chmod` would leave open, where a file meant to land with restrictive
+/// permissions briefly sits on disk with the default (umask) ones.
+///
+/// `open(2)`'s mode argument is masked by the process umask like any other
+/// file creation, so the bits it actually lands with can come out narrower
+/// than `mode` (never wider — it's still safe from the TOCTOU angle this
+/// exists to close). Following up with an explicit `fchmod` via
+/// `File::set_permissions` forces the final mode to match `mode` exactly,
+/// without reopening any window of excess exposure: the file was already at
+/// least as restrictive as the target the whole time.
+#[cfg(unix)]
+fn write_with_mode(
+    path: &std::path::Path,
+    content: &str,
+    mode: Option<u32>,
+) -> Result<(), ObfuscationError> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut open_options = fs::OpenOptions::new();
+    open_options.write(true).create(true).truncate(true);
+    if let Some(mode) = mode {
+        open_options.mode(mode);
+    }
+
+    let mut file = open_options.open(path)?;
+    file.write_all(content.as_bytes())?;
+
+    if let Some(mode) = mode {
+        file.set_permissions(fs::Permissions::from_mode(mode))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_with_mode(
+    path: &std::path::Path,
+    content: &str,
+    _mode: Option<u32>,
+) -> Result<(), ObfuscationError> {
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Applies `options`' owner/group to `path` right after it is written. POSIX
+/// has no atomic create-and-chown equivalent, so this step necessarily runs
+/// after the file exists; the permission mode itself is handled atomically
+/// by `write_with_mode` instead, since that's the bit a restrictive-mode
+/// request actually cares about not leaking briefly.
+#[cfg(unix)]
+fn apply_file_ownership(path: &std::path::Path, options: &FileOptions) -> Result<(), ObfuscationError> {
+    if options.owner.is_some() || options.group.is_some() {
+        let uid = options.owner.map(nix::unistd::Uid::from_raw);
+        let gid = options.group.map(nix::unistd::Gid::from_raw);
+        nix::unistd::chown(path, uid, gid).map_err(|e| {
+            ObfuscationError::new(ErrorKind::IoError, "Failed to set file ownership")
+                .with_details(e.to_string())
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn apply_file_ownership(_path: &std::path::Path, _options: &FileOptions) -> Result<(), ObfuscationError> {
+    // Ownership has no meaningful equivalent on Windows.
     Ok(())
 }
 
@@ -131,10 +238,11 @@ fn load_file(path: String) -> Result<String, ObfuscationError> {
 }
 
 #[tauri::command]
-fn compile_code(code: String, output_path: String) -> Result<String, ObfuscationError> {
-    // TODO: Spawn g++/clang compiler
-    // For now, mock compilation with basic validation
-    
+fn compile_code(
+    code: String,
+    output_path: String,
+    config: Option<ObfuscationConfig>,
+) -> Result<String, ObfuscationError> {
     if code.trim().is_empty() {
         return Err(ObfuscationError::new(
             ErrorKind::CompilationError,
@@ -142,6 +250,7 @@ fn compile_code(code: String, output_path: String) -> Result<String, Obfuscation
         ));
     }
 
+    let config = config.unwrap_or_default();
     let output = PathBuf::from(output_path);
     if let Some(parent) = output.parent() {
         if !parent.exists() {
@@ -149,7 +258,41 @@ fn compile_code(code: String, output_path: String) -> Result<String, Obfuscation
         }
     }
 
-    Ok(format!("Mock compilation successful: {}", output.display()))
+    // The compiler needs a real translation unit on disk; write the
+    // (possibly obfuscated) source next to the requested output.
+    let source_path = output.with_extension("obf.cpp");
+    fs::write(&source_path, &code)?;
+    let result = compiler::compile(
+        &source_path,
+        &output,
+        &config.compiler,
+        config.preserve_debug_info,
+    );
+    let _ = fs::remove_file(&source_path);
+
+    match result {
+        Ok(compiled) => Ok(format!(
+            "Compilation successful: {}\n{}",
+            compiled.output_path, compiled.stdout
+        )),
+        Err(compiler::CompilerError::ToolchainNotFound(msg)) => Err(ObfuscationError::new(
+            ErrorKind::CompilationError,
+            "Compiler toolchain not found",
+        )
+        .with_details(msg)),
+        Err(compiler::CompilerError::CompilationFailed { exit_code, stderr }) => {
+            Err(ObfuscationError::new(
+                ErrorKind::CompilationError,
+                format!("Compilation failed with exit code {:?}", exit_code),
+            )
+            .with_details(stderr))
+        }
+        Err(compiler::CompilerError::Io(msg)) => Err(ObfuscationError::new(
+            ErrorKind::IoError,
+            "Failed to invoke compiler",
+        )
+        .with_details(msg)),
+    }
 }
 
 #[tauri::command]
@@ -181,15 +324,38 @@ fn save_config(
 }
 
 #[tauri::command]
-fn get_default_config() -> ObfuscationConfig {
-    ObfuscationConfig::default()
+fn get_default_config() -> Result<ResolvedConfig, ObfuscationError> {
+    // Resolving with no input path or overrides still walks the system/user
+    // layers, so the UI can show *why* a default was overridden on this machine.
+    config::resolve_config(None, None).map_err(|e| {
+        ObfuscationError::new(ErrorKind::ConfigurationError, "Failed to resolve configuration")
+            .with_details(e.to_string())
+    })
+}
+
+#[tauri::command]
+fn get_config_schema() -> serde_json::Value {
+    config::config_schema()
+}
+
+#[tauri::command]
+fn resolve_config(
+    input_path: Option<String>,
+    overrides: Option<PartialObfuscationConfig>,
+) -> Result<ResolvedConfig, ObfuscationError> {
+    let input_path = input_path.map(PathBuf::from);
+
+    config::resolve_config(input_path.as_deref(), overrides).map_err(|e| {
+        ObfuscationError::new(ErrorKind::ConfigurationError, "Failed to resolve configuration")
+            .with_details(e.to_string())
+    })
 }
 
 // Enhanced mock obfuscation with configuration support
 fn mock_obfuscate(
     code: &str,
     config: &ObfuscationConfig,
-) -> Result<String, ObfuscationError> {
+) -> Result<(String, ObfuscationReport), ObfuscationError> {
     if code.trim().is_empty() {
         return Err(ObfuscationError::new(
             ErrorKind::ObfuscationError,
@@ -198,7 +364,9 @@ fn mock_obfuscate(
     }
 
     let mut obfuscated = String::new();
-    
+    let mut techniques = TechniqueReport::default();
+    let mut prng = Prng::resolve(config.seed);
+
     // Add header with configuration info
     obfuscated.push_str(&format!(
         "/* === OBFUSCATED CODE (Intensity: {:?}) === */\n",
@@ -206,10 +374,24 @@ fn mock_obfuscate(
     ));
 
     let mut transformed = code.to_string();
-
-    // Apply transformations based on configuration
+    let mut string_encryption_header = String::new();
+
+    // Apply transformations based on configuration, driving every
+    // randomized choice through `prng` so the same seed reproduces
+    // byte-identical output.
+    //
+    // String encryption's injected `_obf_decrypt` helper and literal arrays
+    // are kept out of `transformed` until instruction substitution has run: later
+    // passes like instruction substitution do naive whole-buffer token
+    // replacement, and that helper's own source (e.g. its `return`
+    // statement) would otherwise get mangled right along with the user's
+    // code, producing invalid C++.
     if config.techniques.string_encryption {
         obfuscated.push_str("/* String encryption: ENABLED */\n");
+        let result = string_encryption::encrypt_string_literals(&transformed, &mut prng);
+        transformed = result.body;
+        string_encryption_header = result.header;
+        techniques.string_encryption_sites = result.sites_transformed;
     }
 
     if config.techniques.control_flow_flattening {
@@ -219,11 +401,20 @@ fn mock_obfuscate(
     if config.techniques.instruction_substitution {
         obfuscated.push_str("/* Instruction substitution: ENABLED */\n");
         // Mock instruction substitution
-        transformed = transformed
-            .replace("main", "_0x4d61696e")
-            .replace("std::cout", "_0x636f7574")
-            .replace("return", "_0x72657475726e")
-            .replace("int ", "_0x696e7420");
+        const SUBSTITUTIONS: [(&str, &str); 4] = [
+            ("main", "_0x4d61696e"),
+            ("std::cout", "_0x636f7574"),
+            ("return", "_0x72657475726e"),
+            ("int ", "_0x696e7420"),
+        ];
+        for (needle, replacement) in SUBSTITUTIONS {
+            techniques.instruction_substitution_sites += transformed.matches(needle).count();
+            transformed = transformed.replace(needle, replacement);
+        }
+    }
+
+    if !string_encryption_header.is_empty() {
+        transformed.insert_str(0, &string_encryption_header);
     }
 
     if config.techniques.bogus_code_injection {
@@ -232,13 +423,34 @@ fn mock_obfuscate(
             0,
             "volatile int _obf_dummy = 0;\n#define _OBF_NOP() do { _obf_dummy++; } while(0)\n\n",
         );
+        techniques.bogus_code_injection_sites += 1;
     }
 
     obfuscated.push('\n');
     obfuscated.push_str(&transformed);
     obfuscated.push_str("\n/* === END OBFUSCATED === */");
 
-    Ok(obfuscated)
+    let original_size = code.len();
+    let obfuscated_size = obfuscated.len();
+    let size_budget_warning = config.max_size_increase.and_then(|max_increase| {
+        let increase_pct = (obfuscated_size as f64 / original_size as f64) * 100.0;
+        (increase_pct > max_increase as f64).then(|| {
+            format!(
+                "Obfuscated output is {:.1}% of the original size, exceeding the configured maximum of {}%",
+                increase_pct, max_increase
+            )
+        })
+    });
+
+    let report = ObfuscationReport {
+        techniques,
+        original_size,
+        obfuscated_size,
+        effective_seed: Some(prng.seed()),
+        size_budget_warning,
+    };
+
+    Ok((obfuscated, report))
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -255,7 +467,114 @@ pub fn run() {
             load_config,
             save_config,
             get_default_config,
+            resolve_config,
+            get_config_schema,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_obfuscate_code_default_config_does_not_mangle_decrypt_helper() {
+        let code = r#"int main() { std::cout << "hello, world"; return 0; }"#;
+        let config = ObfuscationConfig::default();
+
+        let (obfuscated, _) = mock_obfuscate(code, &config).unwrap();
+
+        // With string_encryption and instruction_substitution both on (the
+        // default), the injected `_obf_decrypt` helper's own `return`
+        // statement must survive the later substitution pass intact, even
+        // though the user's own `return 0;` is expected to get mangled by
+        // the (deliberately crude) substitution table like everything else.
+        assert!(obfuscated.contains("return buf;"));
+        assert!(!obfuscated.contains("_0x72657475726e buf;"));
+    }
+
+    #[test]
+    fn test_obfuscate_code_multi_technique_output_has_no_orphaned_identifiers() {
+        // A broader sanity check alongside the regression test above: a
+        // source with no string literals at all (so string_encryption never
+        // injects a helper) shouldn't produce that helper's body either.
+        let code = "int main() { return 0; }";
+        let config = ObfuscationConfig::default();
+
+        let (obfuscated, _) = mock_obfuscate(code, &config).unwrap();
+
+        assert!(!obfuscated.contains("_obf_decrypt"));
+    }
+
+    #[test]
+    fn test_obfuscate_code_report_counts_sites_per_technique() {
+        let code = r#"int main() { std::cout << "hello"; std::cout << "world"; return 0; }"#;
+        let config = ObfuscationConfig::default();
+
+        let (_, report) = mock_obfuscate(code, &config).unwrap();
+
+        assert_eq!(report.techniques.string_encryption_sites, 2);
+        // "main", "std::cout" (x2), "return", "int " each count as a site.
+        assert_eq!(report.techniques.instruction_substitution_sites, 5);
+        assert_eq!(report.techniques.bogus_code_injection_sites, 1);
+    }
+
+    #[test]
+    fn test_obfuscate_code_size_budget_warning_fires_when_exceeded() {
+        let code = "int main() { return 0; }";
+        let mut config = ObfuscationConfig::default();
+        // The obfuscation header/banners alone push a tiny source well past
+        // 100% of its original size, so this budget is guaranteed to trip.
+        config.max_size_increase = Some(100);
+
+        let (_, report) = mock_obfuscate(code, &config).unwrap();
+
+        assert!(report.size_budget_warning.is_some());
+    }
+
+    #[test]
+    fn test_obfuscate_code_size_budget_warning_silent_when_within_budget() {
+        let code = "int main() { return 0; }";
+        let mut config = ObfuscationConfig::default();
+        config.max_size_increase = Some(100_000);
+
+        let (_, report) = mock_obfuscate(code, &config).unwrap();
+
+        assert!(report.size_budget_warning.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_file_applies_requested_mode_atomically() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "obfussor-save-file-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("secret.txt");
+
+        // 0o666 includes group/other-write bits a typical umask (e.g. 0o022)
+        // would otherwise strip from `open(2)`'s mode argument; asserting on
+        // exactly this value catches a fix that relies on `open` alone
+        // without an explicit chmod to match the requested bits.
+        let options = FileOptions {
+            mode: Some(0o666),
+            owner: None,
+            group: None,
+        };
+        save_file(
+            path.to_string_lossy().into_owned(),
+            "top secret".to_string(),
+            Some(options),
+        )
+        .unwrap();
+
+        let actual_mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(actual_mode, 0o666);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,52 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// A seeded PRNG shared across obfuscation passes so that, given the same
+/// input, config, and seed, every randomized choice comes out identical.
+///
+/// Built on `rand_chacha::ChaCha8Rng` rather than `rand::rngs::StdRng`:
+/// `StdRng`'s algorithm is an implementation detail the `rand` crate
+/// explicitly reserves the right to change between releases, so a plain
+/// `cargo update` could silently change every seeded output. `ChaCha8Rng` is
+/// a named, fixed algorithm with no such escape hatch, so the same seed
+/// keeps producing the same bytes across `rand`/`rand_chacha` upgrades.
+pub struct Prng {
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl Prng {
+    /// Builds a PRNG from an explicit seed.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Builds a PRNG from a freshly generated seed, so the caller can report
+    /// back which seed ended up driving this run.
+    pub fn generate() -> Self {
+        let seed = rand::thread_rng().gen();
+        Self::from_seed(seed)
+    }
+
+    /// Resolves a PRNG from a config's seed: the explicit seed when given,
+    /// otherwise a freshly generated one.
+    pub fn resolve(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Self::from_seed(seed),
+            None => Self::generate(),
+        }
+    }
+
+    /// The seed this PRNG is driven by, for reporting back to the caller.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The next random byte, used e.g. as a per-literal XOR key.
+    pub fn next_byte(&mut self) -> u8 {
+        self.rng.gen()
+    }
+}
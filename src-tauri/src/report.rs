@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-technique counts of how many sites were transformed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TechniqueReport {
+    pub control_flow_flattening_sites: usize,
+    pub string_encryption_sites: usize,
+    pub bogus_code_injection_sites: usize,
+    pub instruction_substitution_sites: usize,
+    pub function_manipulation_sites: usize,
+    pub opaque_predicate_sites: usize,
+}
+
+/// A structured, machine-readable summary of one obfuscation run, produced
+/// when `ObfuscationConfig::generate_report` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObfuscationReport {
+    pub techniques: TechniqueReport,
+    pub original_size: usize,
+    pub obfuscated_size: usize,
+    /// The seed actually used for this run (random-number-derived choices
+    /// are deterministic given the same input, config, and seed)
+    pub effective_seed: Option<u64>,
+    /// Set when `obfuscated_size` exceeds `max_size_increase` of `original_size`
+    pub size_budget_warning: Option<String>,
+}
+
+/// The result of an obfuscation run: the transformed code, plus an optional
+/// report. Callers that only want the code can ignore `report` entirely.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObfuscationResult {
+    pub code: String,
+    pub report: Option<ObfuscationReport>,
+}
@@ -0,0 +1,321 @@
+use crate::prng::Prng;
+
+/// The `_obf_decrypt` helper, emitted once at the top of the file ahead of
+/// any literal it's used by. Each literal gets its own `static const`
+/// payload array *and* its own dedicated `static` output buffer (both
+/// emitted in the header right after this helper, see
+/// `encrypt_string_literals`), XOR-keyed with a single byte. Decrypting into
+/// a buffer dedicated to one call site (rather than one shared buffer) is
+/// what keeps multiple live decrypted strings from clobbering one another.
+const DECRYPT_HELPER: &str = "\
+#include <cstddef>
+
+static inline const char* _obf_decrypt(const unsigned char* data, char* buf, size_t len, unsigned char key) {
+    for (size_t i = 0; i < len; i++) {
+        buf[i] = (char)(data[i] ^ key);
+    }
+    buf[len] = '\\0';
+    return buf;
+}
+
+";
+
+/// Result of running the string-encryption pass over one source file.
+///
+/// `header` and `body` are kept separate (rather than pre-joined into one
+/// string) so callers can run further text-level passes over `body` without
+/// those passes also seeing — and potentially mangling — the injected
+/// `_obf_decrypt` helper and literal payload arrays in `header`.
+pub struct StringEncryptionResult {
+    pub header: String,
+    pub body: String,
+    pub sites_transformed: usize,
+}
+
+/// Scans `source` for double-quoted C/C++ string literals and replaces each
+/// non-empty one (`""` is left as is) with a call to `_obf_decrypt` over a byte array XOR-keyed with a
+/// fresh key drawn from `prng`. Skips string-literal-looking text inside
+/// `//` and `/* */` comments and inside `'...'` char literals, and correctly
+/// walks past `\"` without ending the literal early.
+pub fn encrypt_string_literals(source: &str, prng: &mut Prng) -> StringEncryptionResult {
+    let bytes = source.as_bytes();
+    let mut literals = Vec::new();
+    let mut body = String::with_capacity(source.len());
+    let mut plain_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i = (i + 2).min(bytes.len());
+            }
+            b'\'' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'\'' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+            }
+            b'"' => {
+                body.push_str(&source[plain_start..i]);
+
+                let literal_start = i;
+                i += 1;
+                let mut decoded = Vec::new();
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' && i + 1 < bytes.len() {
+                        let (escaped_bytes, consumed) = decode_escape(bytes, i + 1);
+                        decoded.extend(escaped_bytes);
+                        i += 1 + consumed;
+                    } else {
+                        decoded.push(bytes[i]);
+                        i += 1;
+                    }
+                }
+                i = (i + 1).min(bytes.len());
+
+                if decoded.is_empty() {
+                    body.push_str(&source[literal_start..i]);
+                } else {
+                    let key = prng.next_byte();
+                    let index = literals.len();
+                    let encrypted: Vec<u8> = decoded.iter().map(|b| b ^ key).collect();
+                    body.push_str(&format!(
+                        "_obf_decrypt(_obf_lit_{0}, _obf_buf_{0}, {1}, 0x{2:02x})",
+                        index,
+                        decoded.len(),
+                        key
+                    ));
+                    literals.push((index, encrypted));
+                }
+
+                plain_start = i;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    body.push_str(&source[plain_start..]);
+
+    if literals.is_empty() {
+        return StringEncryptionResult {
+            header: String::new(),
+            body: source.to_string(),
+            sites_transformed: 0,
+        };
+    }
+
+    let mut header = String::new();
+    header.push_str(DECRYPT_HELPER);
+    for (index, encrypted) in &literals {
+        let bytes_literal = encrypted
+            .iter()
+            .map(|b| format!("0x{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(", ");
+        header.push_str(&format!(
+            "static const unsigned char _obf_lit_{0}[] = {{{1}}};\nstatic char _obf_buf_{0}[{2}];\n",
+            index,
+            bytes_literal,
+            encrypted.len() + 1
+        ));
+    }
+    header.push('\n');
+
+    StringEncryptionResult {
+        header,
+        body,
+        sites_transformed: literals.len(),
+    }
+}
+
+/// Decodes the escape sequence starting at `bytes[at]` (the character right
+/// after the backslash), returning the runtime bytes it produces and how
+/// many source bytes starting at `at` belong to it. Handles the fixed
+/// single-character escapes, `\NNN` octal (up to 3 digits), `\xNN...` hex
+/// (as many hex digits as follow), and `\uXXXX`/`\UXXXXXXXX` Unicode
+/// escapes (encoded as UTF-8). Anything unrecognized, or a `\u`/`\U`/`\x`
+/// without the digits it requires, passes through as a single literal byte,
+/// which keeps the transform conservative rather than silently wrong.
+fn decode_escape(bytes: &[u8], at: usize) -> (Vec<u8>, usize) {
+    match bytes[at] {
+        b'n' => (vec![b'\n'], 1),
+        b't' => (vec![b'\t'], 1),
+        b'r' => (vec![b'\r'], 1),
+        b'a' => (vec![0x07], 1),
+        b'b' => (vec![0x08], 1),
+        b'f' => (vec![0x0c], 1),
+        b'v' => (vec![0x0b], 1),
+        b'\\' => (vec![b'\\'], 1),
+        b'\'' => (vec![b'\''], 1),
+        b'"' => (vec![b'"'], 1),
+        b'x' => {
+            let digits_start = at + 1;
+            let mut end = digits_start;
+            while end < bytes.len() && bytes[end].is_ascii_hexdigit() {
+                end += 1;
+            }
+            if end == digits_start {
+                (vec![b'x'], 1)
+            } else {
+                (vec![hex_value(&bytes[digits_start..end]) as u8], end - at)
+            }
+        }
+        b'0'..=b'7' => {
+            let mut end = at;
+            let mut value: u32 = 0;
+            while end < bytes.len() && end - at < 3 && (b'0'..=b'7').contains(&bytes[end]) {
+                value = value * 8 + (bytes[end] - b'0') as u32;
+                end += 1;
+            }
+            (vec![value as u8], end - at)
+        }
+        b'u' => decode_unicode_escape(bytes, at, 4),
+        b'U' => decode_unicode_escape(bytes, at, 8),
+        other => (vec![other], 1),
+    }
+}
+
+/// Decodes a `\u`/`\U` escape: exactly `digit_count` hex digits after the
+/// introducing character, encoded to UTF-8. Falls back to passing the
+/// introducing character through as-is if the required digits aren't there.
+fn decode_unicode_escape(bytes: &[u8], at: usize, digit_count: usize) -> (Vec<u8>, usize) {
+    let digits_start = at + 1;
+    let digits_end = digits_start + digit_count;
+    let has_enough_digits = digits_end <= bytes.len()
+        && bytes[digits_start..digits_end].iter().all(u8::is_ascii_hexdigit);
+    if !has_enough_digits {
+        return (vec![bytes[at]], 1);
+    }
+
+    let code_point = hex_value(&bytes[digits_start..digits_end]);
+    let mut out = Vec::new();
+    if let Some(c) = char::from_u32(code_point) {
+        let mut buf = [0u8; 4];
+        out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+    (out, digits_end - at)
+}
+
+/// Parses `digits` (ASCII hex digits, already validated by the caller) into
+/// its numeric value.
+fn hex_value(digits: &[u8]) -> u32 {
+    digits.iter().fold(0u32, |acc, &b| {
+        acc * 16 + (b as char).to_digit(16).unwrap_or(0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_and_char_literals_are_left_alone() {
+        let mut prng = Prng::from_seed(1);
+        let result = encrypt_string_literals("char c = 'x'; const char* e = \"\";", &mut prng);
+        assert_eq!(result.sites_transformed, 0);
+        assert!(result.header.is_empty());
+        assert!(result.body.contains("'x'"));
+    }
+
+    #[test]
+    fn test_same_seed_is_deterministic() {
+        let source = r#"std::cout << "hello, world";"#;
+        let mut prng_a = Prng::from_seed(42);
+        let mut prng_b = Prng::from_seed(42);
+
+        let a = encrypt_string_literals(source, &mut prng_a);
+        let b = encrypt_string_literals(source, &mut prng_b);
+
+        assert_eq!(a.header, b.header);
+        assert_eq!(a.body, b.body);
+        assert_eq!(a.sites_transformed, 1);
+    }
+
+    #[test]
+    fn test_skips_literal_inside_comment_and_char_literal() {
+        let source = "// \"not a literal\"\nchar q = '\"'; const char* s = \"real\";";
+        let mut prng = Prng::from_seed(7);
+        let result = encrypt_string_literals(source, &mut prng);
+        assert_eq!(result.sites_transformed, 1);
+    }
+
+    #[test]
+    fn test_escaped_quote_does_not_end_literal_early() {
+        let source = r#"const char* s = "a\"b";"#;
+        let mut prng = Prng::from_seed(3);
+        let result = encrypt_string_literals(source, &mut prng);
+        assert_eq!(result.sites_transformed, 1);
+        // The decoded literal is 3 bytes (a, ", b); confirm the length
+        // passed to `_obf_decrypt` is exactly that.
+        assert!(result.body.contains("_obf_decrypt(_obf_lit_0, _obf_buf_0, 3,"));
+    }
+
+    #[test]
+    fn test_header_contains_decrypt_helper_and_is_kept_separate_from_body() {
+        let source = r#"std::cout << "hello, world";"#;
+        let mut prng = Prng::from_seed(9);
+        let result = encrypt_string_literals(source, &mut prng);
+        assert!(result.header.contains("static inline const char* _obf_decrypt"));
+        assert!(!result.body.contains("static inline"));
+        assert!(result.body.contains("_obf_decrypt(_obf_lit_0"));
+    }
+
+    #[test]
+    fn test_header_includes_cstddef_for_size_t() {
+        let source = r#"const char* s = "needs size_t";"#;
+        let mut prng = Prng::from_seed(11);
+        let result = encrypt_string_literals(source, &mut prng);
+        assert!(result.header.contains("#include <cstddef>"));
+    }
+
+    #[test]
+    fn test_each_literal_gets_its_own_buffer() {
+        let source = r#"f("aaaa", "bbbb");"#;
+        let mut prng = Prng::from_seed(5);
+        let result = encrypt_string_literals(source, &mut prng);
+        assert_eq!(result.sites_transformed, 2);
+        assert!(result.header.contains("_obf_buf_0["));
+        assert!(result.header.contains("_obf_buf_1["));
+        assert!(result.body.contains("_obf_decrypt(_obf_lit_0, _obf_buf_0,"));
+        assert!(result.body.contains("_obf_decrypt(_obf_lit_1, _obf_buf_1,"));
+    }
+
+    #[test]
+    fn test_hex_escape_is_decoded_to_one_byte() {
+        let source = r#"const char* s = "\x1b[31mRED\x1b[0m";"#;
+        let mut prng = Prng::from_seed(13);
+        let result = encrypt_string_literals(source, &mut prng);
+        // \x1b[31mRED\x1b[0m decodes to 12 runtime bytes (two 1-byte \x
+        // escapes replacing 4 source chars each, plus 10 literal chars).
+        assert!(result.header.contains("_obf_buf_0[13]"));
+    }
+
+    #[test]
+    fn test_octal_escape_is_decoded() {
+        let source = r#"const char* s = "\101\102";"#;
+        let mut prng = Prng::from_seed(17);
+        let result = encrypt_string_literals(source, &mut prng);
+        // \101 and \102 each decode to one byte ('A' and 'B').
+        assert!(result.header.contains("_obf_buf_0[3]"));
+    }
+
+    #[test]
+    fn test_unicode_escape_is_decoded_to_utf8() {
+        let source = r#"const char* s = "\u00e9";"#;
+        let mut prng = Prng::from_seed(19);
+        let result = encrypt_string_literals(source, &mut prng);
+        // \u00e9 (e-acute) decodes to 2 UTF-8 bytes.
+        assert!(result.header.contains("_obf_buf_0[3]"));
+    }
+}